@@ -2,11 +2,10 @@
 //!
 //! This example shows how to use your existing Axum Router over HTTP/3:
 //! - Build your Axum Router with all its ergonomics (extractors, state, etc.)
-//! - Use h3_axum::serve_h3_with_axum() to transport it over HTTP/3
-//! - Use h3_axum::is_graceful_h3_close() for proper error handling
+//! - Use h3_axum::serve() to run the accept loop with graceful shutdown
 //!
 //! The key line is just:
-//!   h3_axum::serve_h3_with_axum(app, resolver).await?;
+//!   h3_axum::serve(endpoint, app).with_graceful_shutdown(shutdown_signal()).await;
 //!
 //! That's it! Your Axum router now speaks HTTP/3.
 //!
@@ -22,7 +21,6 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use bytes::Bytes;
 use h3_quinn::quinn;
 use serde::{Deserialize, Serialize};
 
@@ -77,7 +75,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/users", get(list_users).post(create_user))
         .route("/users/{id}", get(get_user))
         .route("/echo", post(echo_json))
-        .with_state(app_state);
+        .with_state(app_state)
+        // Advertised on every response, whether served over H3 here or
+        // over a companion H1/H2 origin running the same Router.
+        .layer(h3_axum::AltSvcLayer::new(h3_axum::AltSvcBuilder::h3(4433)));
 
     // ========================================================================
     // STANDARD HTTP/3 SERVER SETUP
@@ -129,75 +130,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("  curl --http3-only -k https://localhost:4433/users/123");
     tracing::info!("  curl --http3-only -k https://localhost:4433/users?page=2");
 
-    // Accept connections
-    while let Some(incoming) = endpoint.accept().await {
-        let app = app.clone();
-        tokio::spawn(async move {
-            if let Err(e) = handle_connection(incoming, app).await {
-                tracing::error!("Connection error: {}", e);
-            }
-        });
-    }
+    // h3-axum owns the accept loop: one task per connection, one task per
+    // request, connection errors routed through `is_graceful_h3_close`.
+    // `with_graceful_shutdown` sends GOAWAY and gives in-flight requests a
+    // chance to finish before the endpoint is dropped.
+    h3_axum::serve(endpoint, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await;
 
     Ok(())
 }
 
-async fn handle_connection(
-    incoming: quinn::Incoming,
-    app: Router,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = incoming.await?;
-    let remote_addr = conn.remote_address();
-
-    tracing::info!("New connection from {}", remote_addr);
-
-    // Build H3 connection (standard h3 + h3-quinn integration)
-    // See: https://docs.rs/h3/latest/h3/server/struct.Builder.html
-    // You can configure H3 protocol settings directly here:
-    //   .max_field_section_size(8192) - header size limits
-    //   .send_grease(true) - GREASE for compatibility testing
-    let h3_conn = h3::server::builder()
-        .build(h3_quinn::Connection::new(conn))
-        .await?;
-
-    tokio::pin!(h3_conn);
-
-    // Accept H3 requests (standard h3 API)
-    loop {
-        match h3_conn.accept().await {
-            Ok(Some(resolver)) => {
-                let app = app.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = handle_request(resolver, app).await {
-                        tracing::error!("Request error: {}", e);
-                    }
-                });
-            }
-            Ok(None) => {
-                tracing::info!("Connection closed by peer: {}", remote_addr);
-                break;
-            }
-            Err(e) => {
-                // h3-axum helper: distinguish graceful closes from errors
-                if h3_axum::is_graceful_h3_close(&e) {
-                    tracing::debug!("Connection closed gracefully: {}", remote_addr);
-                } else {
-                    tracing::error!("H3 connection error: {:?}", e);
-                }
-                break;
-            }
-        }
-    }
-
-    Ok(())
-}
-
-async fn handle_request(
-    resolver: h3::server::RequestResolver<h3_quinn::Connection, Bytes>,
-    app: Router,
-) -> Result<(), h3_axum::BoxError> {
-    // Use h3-axum to serve Axum over H3!
-    h3_axum::serve_h3_with_axum(app, resolver).await
+async fn shutdown_signal() {
+    tokio::signal::ctrl_c()
+        .await
+        .expect("failed to install Ctrl+C handler");
+    tracing::info!("Ctrl+C received, starting graceful shutdown");
 }
 
 // ============================================================================
@@ -0,0 +1,171 @@
+//! `Alt-Svc` middleware for advertising HTTP/3 support.
+//!
+//! Clients only discover an HTTP/3 endpoint if a companion HTTP/1.1 or
+//! HTTP/2 origin advertises it with an `Alt-Svc` header, e.g.
+//! `alt-svc: h3=":443"; ma=86400`. [`AltSvcLayer`] is a small `tower::Layer`
+//! that adds that header to every response. Since the same Axum `Router` is
+//! typically served over both the H1/H2 origin and, via this crate, over
+//! H3, applying the layer once to the shared `Router` advertises HTTP/3 on
+//! both without any H3-specific wiring.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use http::{HeaderValue, Request, Response};
+use tower::{Layer, Service};
+
+/// Builds the value of an `Alt-Svc` header from one or more protocol
+/// entries and a max-age.
+///
+/// # Example
+///
+/// ```ignore
+/// use h3_axum::AltSvcBuilder;
+/// use std::time::Duration;
+///
+/// // Renders to: h3=":443"; ma=3600, h3-29=":443"; ma=3600
+/// let value = AltSvcBuilder::h3(443)
+///     .entry("h3-29", 443)
+///     .max_age(Duration::from_secs(3600))
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct AltSvcBuilder {
+    entries: Vec<(String, u16)>,
+    max_age: Duration,
+}
+
+impl AltSvcBuilder {
+    /// Start building an `Alt-Svc` value that advertises HTTP/3 (`h3`) on
+    /// `port`. Chain [`AltSvcBuilder::entry`] to advertise additional
+    /// protocols (e.g. a draft version like `h3-29`) for broader client
+    /// compatibility.
+    pub fn h3(port: u16) -> Self {
+        Self {
+            entries: Vec::new(),
+            max_age: DEFAULT_MAX_AGE,
+        }
+        .entry("h3", port)
+    }
+
+    /// Add another `protocol=":port"` entry.
+    pub fn entry(mut self, protocol: impl Into<String>, port: u16) -> Self {
+        self.entries.push((protocol.into(), port));
+        self
+    }
+
+    /// Override the `ma` (max-age) directive shared by every entry.
+    /// Defaults to 24 hours.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Render the `Alt-Svc` header value.
+    pub fn build(&self) -> HeaderValue {
+        let rendered = self
+            .entries
+            .iter()
+            .map(|(protocol, port)| {
+                format!("{protocol}=\":{port}\"; ma={}", self.max_age.as_secs())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        HeaderValue::from_str(&rendered).expect("Alt-Svc value is valid header ASCII")
+    }
+}
+
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(86400);
+
+/// A [`tower::Layer`] that inserts a configurable `Alt-Svc` header into
+/// every response produced by the wrapped service.
+#[derive(Clone, Debug)]
+pub struct AltSvcLayer {
+    value: HeaderValue,
+}
+
+impl AltSvcLayer {
+    /// Create a layer that advertises the given [`AltSvcBuilder`] value.
+    pub fn new(alt_svc: AltSvcBuilder) -> Self {
+        Self {
+            value: alt_svc.build(),
+        }
+    }
+}
+
+impl<S> Layer<S> for AltSvcLayer {
+    type Service = AltSvcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AltSvcService {
+            inner,
+            value: self.value.clone(),
+        }
+    }
+}
+
+/// The [`tower::Service`] produced by [`AltSvcLayer`].
+#[derive(Clone, Debug)]
+pub struct AltSvcService<S> {
+    inner: S,
+    value: HeaderValue,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for AltSvcService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let value = self.value.clone();
+        // Standard tower clone-and-swap: `self.inner` is the service that
+        // `poll_ready` just reported ready, so move it into the future and
+        // leave a fresh clone (not yet polled) in `self` for the next
+        // `poll_ready`/`call`.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let mut response = inner.call(req).await?;
+            response.headers_mut().insert(http::header::ALT_SVC, value);
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn h3_entry_uses_default_max_age() {
+        let value = AltSvcBuilder::h3(443).build();
+        assert_eq!(value, "h3=\":443\"; ma=86400");
+    }
+
+    #[test]
+    fn multiple_entries_are_comma_separated() {
+        let value = AltSvcBuilder::h3(443).entry("h3-29", 443).build();
+        assert_eq!(value, "h3=\":443\"; ma=86400, h3-29=\":443\"; ma=86400");
+    }
+
+    #[test]
+    fn max_age_overrides_every_entry() {
+        let value = AltSvcBuilder::h3(443)
+            .entry("h3-29", 443)
+            .max_age(Duration::from_secs(3600))
+            .build();
+        assert_eq!(value, "h3=\":443\"; ma=3600, h3-29=\":443\"; ma=3600");
+    }
+}
@@ -0,0 +1,166 @@
+//! Streaming `http_body::Body` adapter over an H3 request stream.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes};
+use h3::quic::RecvStream;
+use h3::server::RequestStream;
+use http_body::{Body, Frame, SizeHint};
+
+use crate::BoxError;
+
+/// The `recv_data` seam [`H3RequestBody`] is generic over.
+///
+/// `h3::server::RequestStream` has no public constructor, so it can't be
+/// built from a fake stream in a test; this trait lets [`H3RequestBody`]'s
+/// `poll_frame` logic be exercised against a fake implementation instead.
+trait RecvData: Send + 'static {
+    fn poll_recv_data(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Bytes>, BoxError>>;
+}
+
+impl<S> RecvData for RequestStream<S, Bytes>
+where
+    S: RecvStream + Send + 'static,
+{
+    fn poll_recv_data(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Bytes>, BoxError>> {
+        RequestStream::poll_recv_data(self, cx).map(|result| {
+            result
+                .map(|chunk| chunk.map(|mut b| b.copy_to_bytes(b.remaining())))
+                .map_err(|e| Box::new(e) as BoxError)
+        })
+    }
+}
+
+/// An [`http_body::Body`] backed by an H3 request stream's `recv_data`.
+///
+/// Each call to `recv_data` is surfaced as a single data [`Frame`], so callers
+/// see request data as it arrives over QUIC rather than after the whole body
+/// has been buffered.
+pub(crate) struct H3RequestBody<T> {
+    stream: Option<T>,
+}
+
+impl<S> H3RequestBody<RequestStream<S, Bytes>>
+where
+    S: RecvStream + Send + 'static,
+{
+    pub(crate) fn new(stream: RequestStream<S, Bytes>) -> Self {
+        Self { stream: Some(stream) }
+    }
+}
+
+impl<T> Body for H3RequestBody<T>
+where
+    T: RecvData + Unpin,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let Some(stream) = this.stream.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match stream.poll_recv_data(cx) {
+            Poll::Ready(Ok(Some(bytes))) => Poll::Ready(Some(Ok(Frame::data(bytes)))),
+            Poll::Ready(Ok(None)) => {
+                this.stream = None;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Err(e)) => {
+                this.stream = None;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    /// A fake [`RecvData`] source that yields preset chunks, then either
+    /// ends the body or fails, depending on how it's constructed.
+    struct FakeStream {
+        chunks: VecDeque<Bytes>,
+        end: Option<BoxError>,
+    }
+
+    impl FakeStream {
+        fn ending(chunks: Vec<&'static str>) -> Self {
+            Self {
+                chunks: chunks.into_iter().map(Bytes::from).collect(),
+                end: None,
+            }
+        }
+
+        fn failing(chunks: Vec<&'static str>, error: &'static str) -> Self {
+            Self {
+                chunks: chunks.into_iter().map(Bytes::from).collect(),
+                end: Some(error.into()),
+            }
+        }
+    }
+
+    impl RecvData for FakeStream {
+        fn poll_recv_data(&mut self, _cx: &mut Context<'_>) -> Poll<Result<Option<Bytes>, BoxError>> {
+            if let Some(chunk) = self.chunks.pop_front() {
+                return Poll::Ready(Ok(Some(chunk)));
+            }
+            match self.end.take() {
+                Some(error) => Poll::Ready(Err(error)),
+                None => Poll::Ready(Ok(None)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_chunks_until_the_stream_ends() {
+        let body = H3RequestBody {
+            stream: Some(FakeStream::ending(vec!["hello ", "world"])),
+        };
+
+        let collected = body.collect().await.expect("body shouldn't error").to_bytes();
+        assert_eq!(collected, Bytes::from_static(b"hello world"));
+    }
+
+    #[tokio::test]
+    async fn empty_stream_yields_no_frames() {
+        let body = H3RequestBody {
+            stream: Some(FakeStream::ending(vec![])),
+        };
+
+        let collected = body.collect().await.expect("body shouldn't error").to_bytes();
+        assert!(collected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stream_error_surfaces_after_prior_chunks() {
+        let mut body = H3RequestBody {
+            stream: Some(FakeStream::failing(vec!["partial"], "stream reset")),
+        };
+
+        let first = body.frame().await.expect("expected a frame").expect("frame shouldn't error");
+        assert_eq!(first.into_data().unwrap(), Bytes::from_static(b"partial"));
+
+        let second = body.frame().await.expect("expected an error frame");
+        assert_eq!(second.unwrap_err().to_string(), "stream reset");
+
+        // Once finished (by end-of-stream or error), further polls yield `None`.
+        assert!(body.frame().await.is_none());
+    }
+}
@@ -0,0 +1,35 @@
+//! Per-connection metadata surfaced to Axum handlers.
+
+use std::net::SocketAddr;
+
+/// Metadata about the underlying QUIC connection, inserted into request
+/// extensions before the request reaches the Axum router.
+///
+/// The `remote_addr` field is additionally inserted as
+/// `axum::extract::ConnectInfo<SocketAddr>`, so handlers and middleware that
+/// already use Axum's own `ConnectInfo` extractor (rate limiting, logging,
+/// …) work unchanged over HTTP/3. The rest of this struct is available by
+/// extracting `ConnectInfo` itself, for H3/QUIC-specific needs that Axum has
+/// no extractor for.
+#[derive(Clone, Debug)]
+pub struct ConnectInfo {
+    /// The peer's socket address.
+    pub remote_addr: SocketAddr,
+    /// The ALPN protocol negotiated for this connection, if the TLS stack
+    /// reported one.
+    pub alpn: Option<Vec<u8>>,
+    /// DER-encoded peer certificates presented during the TLS handshake, if
+    /// the server requested client authentication.
+    pub peer_certificates: Option<Vec<Vec<u8>>>,
+}
+
+impl ConnectInfo {
+    /// A [`ConnectInfo`] with only the peer address known.
+    pub fn new(remote_addr: SocketAddr) -> Self {
+        Self {
+            remote_addr,
+            alpn: None,
+            peer_certificates: None,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Replay-safe handling of 0-RTT (early data) requests.
+
+use http::Method;
+
+/// Whether a request arrived in 0-RTT early data, and which policy governs
+/// it.
+///
+/// 0-RTT data is replayable by an on-path attacker, so a request that
+/// arrived before the QUIC handshake was confirmed should not blindly
+/// trigger side-effecting work. This is always inserted into the request's
+/// extensions (even when `is_early_data` is `false`), so handlers and
+/// middleware can look it up and apply their own, more specific, replay
+/// policy on top of (or instead of) the bridge's default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EarlyData {
+    /// Whether this request arrived in 0-RTT early data, i.e. before the
+    /// connection's handshake was confirmed.
+    pub is_early_data: bool,
+    /// The policy applied to this request by [`crate::serve_h3_with_axum`].
+    pub policy: EarlyDataPolicy,
+}
+
+impl EarlyData {
+    /// Whether `method` is safe to execute even if replayed: `GET`, `HEAD`,
+    /// and `OPTIONS` per [RFC 7231 §4.2.1](https://www.rfc-editor.org/rfc/rfc7231#section-4.2.1).
+    pub fn is_replay_safe_method(method: &Method) -> bool {
+        matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+}
+
+/// Policy deciding what happens to a request that arrived as 0-RTT early
+/// data.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum EarlyDataPolicy {
+    /// Reject early-data requests whose method isn't safe/idempotent (see
+    /// [`EarlyData::is_replay_safe_method`]) with `425 Too Early`, without
+    /// dispatching them to the Axum router. This is the default: it
+    /// prevents 0-RTT replay from double-executing side-effecting
+    /// operations.
+    #[default]
+    RejectUnsafeMethods,
+    /// Dispatch every request regardless of its 0-RTT status. [`EarlyData`]
+    /// is still inserted into the request's extensions, so handlers and
+    /// middleware can apply their own policy.
+    LeaveToHandler,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_methods_are_replay_safe() {
+        assert!(EarlyData::is_replay_safe_method(&Method::GET));
+        assert!(EarlyData::is_replay_safe_method(&Method::HEAD));
+        assert!(EarlyData::is_replay_safe_method(&Method::OPTIONS));
+    }
+
+    #[test]
+    fn unsafe_methods_are_not_replay_safe() {
+        assert!(!EarlyData::is_replay_safe_method(&Method::POST));
+        assert!(!EarlyData::is_replay_safe_method(&Method::PUT));
+        assert!(!EarlyData::is_replay_safe_method(&Method::PATCH));
+        assert!(!EarlyData::is_replay_safe_method(&Method::DELETE));
+    }
+}
@@ -8,25 +8,45 @@
 //! ## Quick Start
 //!
 //! ```ignore
-//! use h3_axum::serve_h3_with_axum;
+//! use h3_axum::serve;
 //!
 //! // Your normal Axum router (unchanged!)
 //! let app = Router::new()
 //!     .route("/", get(handler));
 //!
-//! // Serve it over H3 (one line)
-//! serve_h3_with_axum(app, resolver).await?;
+//! // Serve it over H3 (one line), with a Quinn endpoint you configured yourself
+//! serve(endpoint, app).await;
 //! ```
+//!
+//! [`serve`] owns the accept loop (one task per connection, one per request)
+//! and supports graceful shutdown. If you need to drive the loop yourself,
+//! [`serve_h3_with_axum`] resolves and handles a single H3 request from its
+//! `RequestResolver`.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
 use std::error::Error;
 
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use http::{Request, Response};
 use http_body_util::BodyExt;
 
+mod alt_svc;
+mod body;
+mod connect_info;
+mod early_data;
+mod server;
+mod webtransport;
+
+use body::H3RequestBody;
+
+pub use alt_svc::{AltSvcBuilder, AltSvcLayer, AltSvcService};
+pub use connect_info::ConnectInfo;
+pub use early_data::{EarlyData, EarlyDataPolicy};
+pub use server::{serve, Serve};
+pub use webtransport::serve_webtransport_with_axum;
+
 /// Boxed error type
 pub type BoxError = Box<dyn Error + Send + Sync + 'static>;
 
@@ -86,62 +106,99 @@ pub fn is_graceful_h3_close(err: &h3::error::ConnectionError) -> bool {
 ///
 /// ```ignore
 /// use axum::{Router, routing::get};
-/// use h3_axum::serve_h3_with_axum;
+/// use h3_axum::{serve_h3_with_axum, ConnectInfo, EarlyData};
 ///
 /// let app = Router::new()
 ///     .route("/", get(|| async { "Hello H3!" }));
 ///
 /// // When you get an H3 request:
-/// serve_h3_with_axum(app, resolver).await?;
+/// serve_h3_with_axum(app, resolver, connect_info, early_data).await?;
 /// ```
 pub async fn serve_h3_with_axum<Q>(
     app: axum::Router,
     resolver: h3::server::RequestResolver<Q, Bytes>,
+    connect_info: ConnectInfo,
+    early_data: EarlyData,
 ) -> Result<(), BoxError>
 where
     Q: h3::quic::Connection<Bytes>,
+    Q::BidiStream: h3::quic::BidiStream<Bytes>,
+    <Q::BidiStream as h3::quic::BidiStream<Bytes>>::RecvStream: Send + 'static,
 {
-    // Resolve the H3 request
-    let (request_head, mut stream) = resolver.resolve_request().await?;
-
-    // Read request body from H3
-    let mut body_bytes = bytes::BytesMut::new();
-    loop {
-        match stream.recv_data().await {
-            Ok(Some(mut chunk)) => {
-                body_bytes.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
-            }
-            Ok(None) => break,
-            Err(e) => {
-                // Send 400 Bad Request on body read error
-                let mut error_response: Response<()> = Response::new(());
-                *error_response.status_mut() = http::StatusCode::BAD_REQUEST;
-                let _ = stream.send_response(error_response).await;
-                let _ = stream.finish().await;
-                return Err(Box::new(e));
-            }
-        }
-    }
+    let (request_head, stream) = resolver.resolve_request().await?;
+    serve_resolved_h3_request(app, request_head, stream, connect_info, early_data).await
+}
+
+/// Bridge an already-resolved H3 request (head + stream) to Axum.
+///
+/// Split out of [`serve_h3_with_axum`] so callers that need to inspect the
+/// request before committing to the normal Axum path (e.g. to detect a
+/// WebTransport upgrade) can resolve the request themselves and fall back to
+/// this for everything else.
+pub(crate) async fn serve_resolved_h3_request<S>(
+    app: axum::Router,
+    request_head: http::Request<()>,
+    stream: h3::server::RequestStream<S, Bytes>,
+    connect_info: ConnectInfo,
+    early_data: EarlyData,
+) -> Result<(), BoxError>
+where
+    S: h3::quic::BidiStream<Bytes>,
+    S::RecvStream: Send + 'static,
+{
+    // Split the bidirectional stream so the receive half can be driven
+    // independently by the request body while the send half is held here
+    // for the response.
+    let (mut send, recv) = stream.split();
 
-    // Build Axum request
     let (parts, _) = request_head.into_parts();
-    let axum_req = Request::from_parts(parts, axum::body::Body::from(body_bytes.freeze()));
+
+    // 0-RTT data is replayable by an on-path attacker. By default, refuse to
+    // dispatch early-data requests whose method isn't safe/idempotent,
+    // rather than risk double-executing a side-effecting operation.
+    if early_data.is_early_data
+        && matches!(early_data.policy, EarlyDataPolicy::RejectUnsafeMethods)
+        && !EarlyData::is_replay_safe_method(&parts.method)
+    {
+        let mut too_early: Response<()> = Response::new(());
+        *too_early.status_mut() = http::StatusCode::TOO_EARLY;
+        send.send_response(too_early).await?;
+        send.finish().await?;
+        return Ok(());
+    }
+
+    // Build Axum request, wrapping the H3 receive stream so the body is
+    // pulled frame-by-frame instead of buffered up front.
+    let mut axum_req = Request::from_parts(parts, axum::body::Body::new(H3RequestBody::new(recv)));
+
+    // Make connection-level metadata available to extractors and middleware,
+    // mirroring what Axum's own `serve` inserts for TCP connections.
+    axum_req
+        .extensions_mut()
+        .insert(axum::extract::ConnectInfo(connect_info.remote_addr));
+    axum_req.extensions_mut().insert(connect_info);
+    axum_req.extensions_mut().insert(early_data);
 
     // Call Axum router
     let axum_resp = tower::ServiceExt::oneshot(app, axum_req).await?;
 
     // Send response back over H3
-    let (parts, axum_body) = axum_resp.into_parts();
+    let (parts, mut axum_body) = axum_resp.into_parts();
     let head_only: Response<()> = Response::from_parts(parts, ());
-    stream.send_response(head_only).await?;
-
-    // Stream response body
-    let body_bytes = axum_body.collect().await?.to_bytes();
-    if !body_bytes.is_empty() {
-        stream.send_data(body_bytes.into()).await?;
+    send.send_response(head_only).await?;
+
+    // Stream the response body to the client one frame at a time so large or
+    // long-lived (e.g. SSE) responses don't have to be collected in memory.
+    while let Some(frame) = axum_body.frame().await {
+        let frame = frame?;
+        if let Ok(chunk) = frame.into_data() {
+            if !chunk.is_empty() {
+                send.send_data(chunk).await?;
+            }
+        }
     }
 
-    stream.finish().await?;
+    send.finish().await?;
 
     Ok(())
 }
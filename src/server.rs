@@ -0,0 +1,271 @@
+//! High-level accept loop with graceful shutdown, built on Quinn.
+
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use h3_quinn::quinn;
+use tokio::sync::watch;
+
+use crate::{is_graceful_h3_close, serve_h3_with_axum, ConnectInfo, EarlyData, EarlyDataPolicy};
+
+/// How long in-flight requests get to finish after a shutdown signal fires,
+/// before the endpoint is dropped regardless.
+const DEFAULT_SHUTDOWN_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Serve `app` over HTTP/3 on `endpoint`.
+///
+/// This owns the accept loop that every `h3-axum` user previously had to
+/// hand-roll: it spawns a task per connection, a task per request (via
+/// [`serve_h3_with_axum`](crate::serve_h3_with_axum)), and routes connection
+/// errors through [`is_graceful_h3_close`](crate::is_graceful_h3_close).
+///
+/// This accept loop always dispatches resolved requests through
+/// [`serve_h3_with_axum`](crate::serve_h3_with_axum); it does not detect or
+/// handle WebTransport upgrades. A WebTransport CONNECT arriving on a
+/// connection served by [`serve`] is routed to the Axum `Router` like any
+/// other request. If your application needs WebTransport, drive your own
+/// per-connection accept loop and use
+/// [`serve_webtransport_with_axum`](crate::serve_webtransport_with_axum)
+/// directly instead of calling [`serve`].
+///
+/// The returned [`Serve`] implements [`IntoFuture`] and runs the loop once
+/// awaited (or spawned after calling [`IntoFuture::into_future`]).
+/// Call [`Serve::with_graceful_shutdown`] first to arm a shutdown signal.
+///
+/// # Example
+///
+/// ```ignore
+/// h3_axum::serve(endpoint, app)
+///     .with_graceful_shutdown(shutdown_signal())
+///     .await;
+/// ```
+pub fn serve(endpoint: quinn::Endpoint, app: axum::Router) -> Serve {
+    Serve {
+        endpoint,
+        app,
+        shutdown: Box::pin(std::future::pending()),
+        shutdown_deadline: DEFAULT_SHUTDOWN_DEADLINE,
+        early_data_policy: EarlyDataPolicy::default(),
+    }
+}
+
+/// Builder returned by [`serve`]; awaiting it runs the accept loop.
+pub struct Serve {
+    endpoint: quinn::Endpoint,
+    app: axum::Router,
+    shutdown: Pin<Box<dyn Future<Output = ()> + Send>>,
+    shutdown_deadline: Duration,
+    early_data_policy: EarlyDataPolicy,
+}
+
+impl Serve {
+    /// Arm graceful shutdown: when `signal` resolves, the endpoint stops
+    /// accepting new connections, sends `GOAWAY` on every open H3 connection,
+    /// and waits up to the shutdown deadline (default 30s, see
+    /// [`Serve::with_shutdown_deadline`]) for in-flight requests to finish
+    /// before the endpoint is dropped.
+    pub fn with_graceful_shutdown<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown = Box::pin(signal);
+        self
+    }
+
+    /// Override how long in-flight requests get to finish after a shutdown
+    /// signal fires, before the endpoint is dropped regardless. Defaults to
+    /// 30 seconds.
+    pub fn with_shutdown_deadline(mut self, deadline: Duration) -> Self {
+        self.shutdown_deadline = deadline;
+        self
+    }
+
+    /// Override the [`EarlyDataPolicy`] applied to requests that arrive as
+    /// 0-RTT early data. Defaults to [`EarlyDataPolicy::RejectUnsafeMethods`].
+    pub fn with_early_data_policy(mut self, policy: EarlyDataPolicy) -> Self {
+        self.early_data_policy = policy;
+        self
+    }
+}
+
+impl IntoFuture for Serve {
+    type Output = ();
+    type IntoFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(run(
+            self.endpoint,
+            self.app,
+            self.shutdown,
+            self.shutdown_deadline,
+            self.early_data_policy,
+        ))
+    }
+}
+
+async fn run(
+    endpoint: quinn::Endpoint,
+    app: axum::Router,
+    shutdown: Pin<Box<dyn Future<Output = ()> + Send>>,
+    shutdown_deadline: Duration,
+    early_data_policy: EarlyDataPolicy,
+) {
+    let (closing_tx, closing_rx) = watch::channel(false);
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => {
+                tracing::info!("shutdown signal received, no longer accepting new connections");
+                let _ = closing_tx.send(true);
+                break;
+            }
+            incoming = endpoint.accept() => {
+                let Some(incoming) = incoming else { break };
+                let app = app.clone();
+                let closing_rx = closing_rx.clone();
+                tokio::spawn(handle_connection(incoming, app, closing_rx, early_data_policy));
+            }
+        }
+    }
+
+    // Give connections that were sent GOAWAY until the deadline to finish
+    // their in-flight requests and close, then close whatever is left.
+    if tokio::time::timeout(shutdown_deadline, endpoint.wait_idle())
+        .await
+        .is_err()
+    {
+        tracing::warn!(
+            "graceful shutdown deadline ({:?}) elapsed with requests still in flight",
+            shutdown_deadline
+        );
+    }
+    endpoint.close(0u32.into(), b"server shutting down");
+}
+
+/// Build a [`ConnectInfo`] from a Quinn connection's handshake data and peer
+/// identity, once the TLS handshake has completed.
+fn connect_info_for(conn: &quinn::Connection) -> ConnectInfo {
+    let mut info = ConnectInfo::new(conn.remote_address());
+
+    if let Some(data) = conn
+        .handshake_data()
+        .and_then(|data| data.downcast::<quinn::crypto::rustls::HandshakeData>().ok())
+    {
+        info.alpn = data.protocol;
+    }
+
+    if let Some(certs) = conn
+        .peer_identity()
+        .and_then(|identity| identity.downcast::<Vec<rustls::pki_types::CertificateDer<'static>>>().ok())
+    {
+        info.peer_certificates = Some(certs.iter().map(|cert| cert.to_vec()).collect());
+    }
+
+    info
+}
+
+async fn handle_connection(
+    incoming: quinn::Incoming,
+    app: axum::Router,
+    mut closing: watch::Receiver<bool>,
+    early_data_policy: EarlyDataPolicy,
+) {
+    // Accept via 0-RTT when the client attempted it, so the connection (and
+    // any requests on it) are usable immediately. `handshake_confirmed`
+    // flips to `true` once the handshake completes and the server's
+    // acceptance of that early data is confirmed; any request resolved
+    // before then arrived as replayable early data.
+    let connecting = match incoming.accept() {
+        Ok(connecting) => connecting,
+        Err(e) => {
+            tracing::error!("connection setup failed: {}", e);
+            return;
+        }
+    };
+    let (conn, zero_rtt_accepted) = match connecting.into_0rtt() {
+        Ok((conn, zero_rtt_accepted)) => (conn, Some(zero_rtt_accepted)),
+        Err(connecting) => match connecting.await {
+            Ok(conn) => (conn, None),
+            Err(e) => {
+                tracing::error!("connection setup failed: {}", e);
+                return;
+            }
+        },
+    };
+    let remote_addr = conn.remote_address();
+    let handshake_confirmed = Arc::new(AtomicBool::new(zero_rtt_accepted.is_none()));
+
+    // `connect_info` is computed up front so 0-RTT requests (which must be
+    // dispatched before the handshake is confirmed) have something to read.
+    // ALPN and peer-certificate data aren't reliably available yet at that
+    // point, though, so once the handshake is confirmed the spawned task
+    // below recomputes it and publishes the refreshed value here.
+    let (connect_info_tx, connect_info_rx) = watch::channel(connect_info_for(&conn));
+
+    if let Some(zero_rtt_accepted) = zero_rtt_accepted {
+        let flag = handshake_confirmed.clone();
+        let conn = conn.clone();
+        tokio::spawn(async move {
+            zero_rtt_accepted.await;
+            flag.store(true, Ordering::Relaxed);
+            let _ = connect_info_tx.send(connect_info_for(&conn));
+        });
+    }
+
+    let h3_conn = match h3::server::builder()
+        .build(h3_quinn::Connection::new(conn))
+        .await
+    {
+        Ok(conn) => conn,
+        Err(e) => {
+            tracing::error!("h3 handshake with {} failed: {}", remote_addr, e);
+            return;
+        }
+    };
+    tokio::pin!(h3_conn);
+
+    loop {
+        tokio::select! {
+            changed = closing.changed() => {
+                if changed.is_ok() && *closing.borrow() {
+                    // Send GOAWAY, telling the client not to start any
+                    // request beyond the ones it already has in flight.
+                    // This doesn't wait for those in-flight requests (each
+                    // is running in its own spawned task already) — that's
+                    // `run`'s job, via `shutdown_deadline`/`wait_idle`.
+                    let _ = h3_conn.shutdown(0).await;
+                }
+            }
+            result = h3_conn.accept() => {
+                match result {
+                    Ok(Some(resolver)) => {
+                        let app = app.clone();
+                        let connect_info = connect_info_rx.borrow().clone();
+                        let early_data = EarlyData {
+                            is_early_data: !handshake_confirmed.load(Ordering::Relaxed),
+                            policy: early_data_policy,
+                        };
+                        tokio::spawn(async move {
+                            if let Err(e) =
+                                serve_h3_with_axum(app, resolver, connect_info, early_data).await
+                            {
+                                tracing::error!("request error: {}", e);
+                            }
+                        });
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        if !is_graceful_h3_close(&e) {
+                            tracing::error!("h3 connection error from {}: {:?}", remote_addr, e);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! WebTransport session support, built on `h3-webtransport`.
+//!
+//! WebTransport sessions are negotiated via an extended CONNECT request
+//! (`:protocol = webtransport`) and then multiplex further bidirectional
+//! streams, unidirectional streams, and datagrams over the same QUIC
+//! connection. That's a different shape than a regular H3 request/response,
+//! so it's handled separately from [`crate::serve_h3_with_axum`] rather than
+//! forced through the Axum `Router`.
+//!
+//! [`crate::serve`]'s accept loop does not call into this module: it always
+//! dispatches resolved requests through `serve_h3_with_axum`. Applications
+//! that need WebTransport must drive their own per-connection accept loop
+//! and call [`serve_webtransport_with_axum`] directly, as in that function's
+//! example.
+
+use bytes::Bytes;
+use h3::ext::Protocol;
+use h3_datagram::datagram_handler::HandleDatagramsExt;
+use h3_datagram::quic_traits::DatagramConnectionExt;
+use h3_webtransport::server::WebTransportSession;
+
+use crate::{serve_resolved_h3_request, BoxError, ConnectInfo, EarlyData};
+
+/// Accept a single H3 request, dispatching it to `on_session` if it's a
+/// WebTransport upgrade (an extended CONNECT with `:protocol = webtransport`)
+/// or to the normal Axum path otherwise.
+///
+/// `conn` is the same H3 server connection the resolver was produced from; a
+/// WebTransport session needs it to accept additional streams and datagrams
+/// beyond the one carrying the CONNECT request.
+///
+/// # Example
+///
+/// ```ignore
+/// serve_webtransport_with_axum(app, resolver, conn, connect_info, early_data, |session, info| {
+///     tokio::spawn(my_app::run_webtransport_session(session, info));
+/// })
+/// .await?;
+/// ```
+pub async fn serve_webtransport_with_axum<Q>(
+    app: axum::Router,
+    resolver: h3::server::RequestResolver<Q, Bytes>,
+    conn: h3::server::Connection<Q, Bytes>,
+    connect_info: ConnectInfo,
+    early_data: EarlyData,
+    on_session: impl FnOnce(WebTransportSession<Q, Bytes>, ConnectInfo),
+) -> Result<(), BoxError>
+where
+    Q: h3::quic::Connection<Bytes> + Clone + DatagramConnectionExt<Bytes>,
+    Q::BidiStream: h3::quic::BidiStream<Bytes>,
+    <Q::BidiStream as h3::quic::BidiStream<Bytes>>::RecvStream: Send + 'static,
+    h3::server::Connection<Q, Bytes>: HandleDatagramsExt<Q, Bytes>,
+{
+    let (request_head, stream) = resolver.resolve_request().await?;
+
+    if !is_webtransport_connect(&request_head) {
+        return serve_resolved_h3_request(app, request_head, stream, connect_info, early_data).await;
+    }
+
+    // A WebTransport CONNECT only opens a session; it doesn't itself cause
+    // side effects, so it's dispatched regardless of 0-RTT status.
+    let session = WebTransportSession::accept(request_head, stream, conn).await?;
+    on_session(session, connect_info);
+    Ok(())
+}
+
+/// Whether `request_head` is an extended CONNECT asking to upgrade to
+/// WebTransport, per [RFC 9220](https://www.rfc-editor.org/rfc/rfc9220).
+fn is_webtransport_connect(request_head: &http::Request<()>) -> bool {
+    request_head.method() == http::Method::CONNECT
+        && request_head
+            .extensions()
+            .get::<Protocol>()
+            .is_some_and(|protocol| *protocol == Protocol::WEB_TRANSPORT)
+}